@@ -0,0 +1,84 @@
+use super::{CrateDep, EvalFn, Evaluator, Node};
+
+/// A node wrapper that attaches additional crate dependencies to the inner node.
+///
+/// See [`WithCrateDeps::with_crate_deps`].
+#[derive(Clone, Debug)]
+pub struct Deps<N> {
+    node: N,
+    deps: Vec<CrateDep>,
+}
+
+/// Node methods for specifying additional crate dependencies.
+pub trait WithCrateDeps: Node + Sized {
+    /// Specify crate dependencies required by this node's generated code, in addition to any
+    /// already specified by the inner node.
+    ///
+    /// See [`Node::crate_deps`].
+    fn with_crate_deps(self, deps: Vec<CrateDep>) -> Deps<Self> {
+        Deps { node: self, deps }
+    }
+}
+
+impl<N> WithCrateDeps for N where N: Node {}
+
+impl<N> Node for Deps<N>
+where
+    N: Node,
+{
+    fn evaluator(&self) -> Evaluator {
+        self.node.evaluator()
+    }
+
+    fn push_eval(&self) -> Option<EvalFn> {
+        self.node.push_eval()
+    }
+
+    fn pull_eval(&self) -> Option<EvalFn> {
+        self.node.pull_eval()
+    }
+
+    fn state_type(&self) -> Option<syn::Type> {
+        self.node.state_type()
+    }
+
+    fn crate_deps(&self) -> Vec<CrateDep> {
+        self.node
+            .crate_deps()
+            .into_iter()
+            .chain(self.deps.iter().cloned())
+            .collect()
+    }
+
+    fn input_default(&self, input: super::Input) -> Option<syn::Expr> {
+        self.node.input_default(input)
+    }
+
+    fn input_required(&self, input: super::Input) -> bool {
+        self.node.input_required(input)
+    }
+
+    fn input_name(&self, input: super::Input) -> Option<String> {
+        self.node.input_name(input)
+    }
+
+    fn output_name(&self, output: super::Output) -> Option<String> {
+        self.node.output_name(output)
+    }
+
+    fn input_ty_hint(&self, input: super::Input) -> Option<syn::Type> {
+        self.node.input_ty_hint(input)
+    }
+
+    fn output_ty_hint(&self, output: super::Output) -> Option<syn::Type> {
+        self.node.output_ty_hint(output)
+    }
+
+    fn port_docs(&self, port: super::Port) -> Option<String> {
+        self.node.port_docs(port)
+    }
+
+    fn is_async(&self) -> bool {
+        self.node.is_async()
+    }
+}
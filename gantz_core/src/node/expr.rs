@@ -0,0 +1,256 @@
+use super::ExprGen;
+use serde::{Deserialize, Serialize};
+use syn::visit_mut::{self, VisitMut};
+use thiserror::Error;
+
+/// A node whose evaluation is described by a single Rust expression rather than a named
+/// function.
+///
+/// An `Expr` may be constructed in one of two ways:
+///
+/// - [`Expr::new`] parses the expression from a `str` template in which each input is referred
+///   to via a reserved placeholder ident of the form `__gantz_inN` (e.g. `"__gantz_in0 +
+///   __gantz_in1"`). The template is retained as data, so `Expr` nodes constructed this way may
+///   be (de)serialized and so can round-trip through [`SerdeNode`][super::SerdeNode].
+/// - [`Expr::new_fn`] accepts an arbitrary closure for producing the expression from its input
+///   expressions. This is more flexible, but the resulting `Expr` cannot be serialized.
+#[derive(Clone)]
+pub struct Expr {
+    gen_expr: ExprGen,
+    n_inputs: u32,
+    n_outputs: u32,
+}
+
+/// An error indicating that the given `str` could not be parsed as a valid `Expr` template.
+#[derive(Debug, Error)]
+pub enum NewExprError {
+    /// The given `str` was not a valid Rust expression.
+    #[error("failed to parse the given `str` as a `syn::Expr`: {0}")]
+    Parse(#[from] syn::Error),
+}
+
+/// The prefix of the reserved placeholder idents used to mark an `Expr` template's inputs, e.g.
+/// `__gantz_in0`, `__gantz_in1`, etc.
+const PLACEHOLDER_PREFIX: &str = "__gantz_in";
+
+impl Expr {
+    /// Create a new, serializable `Expr` node by parsing `expr_str` as a Rust expression
+    /// template.
+    ///
+    /// Each input is referred to within the template via a reserved placeholder ident of the
+    /// form `__gantz_inN`, e.g. `"__gantz_in0 + __gantz_in1"`. The number of inputs is inferred
+    /// as one greater than the highest placeholder index present in the template. There is
+    /// always exactly one output - the value produced by evaluating the expression.
+    pub fn new(expr_str: &str) -> Result<Self, NewExprError> {
+        let template: syn::Expr = syn::parse_str(expr_str)?;
+        let n_inputs = placeholder_count(&template);
+        let n_outputs = 1;
+        Ok(Expr {
+            gen_expr: ExprGen::Template(Box::new(template)),
+            n_inputs,
+            n_outputs,
+        })
+    }
+
+    /// Create a new `Expr` node from a closure that produces the output expression given the
+    /// input expressions.
+    ///
+    /// This is a more flexible alternative to [`Expr::new`], but the resulting `Expr` cannot be
+    /// serialized - prefer `Expr::new` wherever the expression can be expressed as a template.
+    pub fn new_fn<F>(n_inputs: u32, n_outputs: u32, gen_expr: F) -> Self
+    where
+        F: Fn(Vec<syn::Expr>) -> syn::Expr + 'static,
+    {
+        Expr {
+            gen_expr: ExprGen::Fn(std::rc::Rc::new(gen_expr)),
+            n_inputs,
+            n_outputs,
+        }
+    }
+
+    /// The number of inputs to the expression.
+    pub fn n_inputs(&self) -> u32 {
+        self.n_inputs
+    }
+
+    /// The number of outputs to the expression.
+    pub fn n_outputs(&self) -> u32 {
+        self.n_outputs
+    }
+}
+
+impl std::fmt::Debug for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Expr")
+            .field("n_inputs", &self.n_inputs)
+            .field("n_outputs", &self.n_outputs)
+            .finish()
+    }
+}
+
+impl super::Node for Expr {
+    fn evaluator(&self) -> super::Evaluator {
+        super::Evaluator::Expr {
+            gen_expr: self.gen_expr.clone(),
+            n_inputs: self.n_inputs,
+            n_outputs: self.n_outputs,
+        }
+    }
+}
+
+// Whether or not the given path refers to a reserved `__gantz_inN` placeholder, returning `N` if
+// so.
+fn placeholder_index(path: &syn::Path) -> Option<u32> {
+    if path.leading_colon.is_some() || path.segments.len() != 1 {
+        return None;
+    }
+    let ident = &path.segments[0].ident;
+    ident
+        .to_string()
+        .strip_prefix(PLACEHOLDER_PREFIX)?
+        .parse()
+        .ok()
+}
+
+// A visitor that tracks the highest placeholder index encountered within an `Expr` template.
+struct CountPlaceholders {
+    max_index: Option<u32>,
+}
+
+impl VisitMut for CountPlaceholders {
+    fn visit_expr_path_mut(&mut self, node: &mut syn::ExprPath) {
+        if let Some(i) = placeholder_index(&node.path) {
+            self.max_index = Some(self.max_index.map_or(i, |m| m.max(i)));
+        }
+        visit_mut::visit_expr_path_mut(self, node);
+    }
+}
+
+// The number of inputs referred to by placeholders within the given template.
+fn placeholder_count(template: &syn::Expr) -> u32 {
+    let mut template = template.clone();
+    let mut visitor = CountPlaceholders { max_index: None };
+    visitor.visit_expr_mut(&mut template);
+    visitor.max_index.map_or(0, |m| m + 1)
+}
+
+// A visitor that substitutes each `__gantz_inN` placeholder with its corresponding input
+// expression.
+struct Substitute<'a> {
+    args: &'a [syn::Expr],
+}
+
+impl<'a> VisitMut for Substitute<'a> {
+    fn visit_expr_mut(&mut self, node: &mut syn::Expr) {
+        if let syn::Expr::Path(expr_path) = node {
+            if let Some(i) = placeholder_index(&expr_path.path) {
+                if let Some(arg) = self.args.get(i as usize) {
+                    *node = arg.clone();
+                    return;
+                }
+            }
+        }
+        visit_mut::visit_expr_mut(self, node);
+    }
+}
+
+/// Produce the final expression by substituting each `__gantz_inN` placeholder within `template`
+/// with its corresponding expression from `args`.
+///
+/// `args[i]` is substituted for the input at `Input(i)`. Used by [`super::Evaluator::expr`].
+pub(crate) fn substitute(template: &syn::Expr, args: &[syn::Expr]) -> syn::Expr {
+    let mut template = template.clone();
+    let mut visitor = Substitute { args };
+    visitor.visit_expr_mut(&mut template);
+    template
+}
+
+// The serializable representation of a template-based `Expr`. `ExprGen::Fn` has no equivalent
+// and is rejected at serialization time.
+#[derive(Serialize, Deserialize)]
+struct SerdeExpr {
+    #[serde(with = "crate::node::serde::expr")]
+    template: syn::Expr,
+    n_inputs: u32,
+    n_outputs: u32,
+}
+
+impl Serialize for Expr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.gen_expr {
+            ExprGen::Template(ref template) => {
+                let data = SerdeExpr {
+                    template: (**template).clone(),
+                    n_inputs: self.n_inputs,
+                    n_outputs: self.n_outputs,
+                };
+                data.serialize(serializer)
+            }
+            ExprGen::Fn(_) => Err(serde::ser::Error::custom(
+                "cannot serialize an `Expr` node constructed via `Expr::new_fn`; \
+                 use `Expr::new` to construct a serializable, template-based `Expr`",
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Expr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = SerdeExpr::deserialize(deserializer)?;
+        Ok(Expr {
+            gen_expr: ExprGen::Template(Box::new(data.template)),
+            n_inputs: data.n_inputs,
+            n_outputs: data.n_outputs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_infers_n_inputs_from_highest_placeholder() {
+        let expr = Expr::new("__gantz_in0 + __gantz_in2").unwrap();
+        assert_eq!(expr.n_inputs(), 3);
+        assert_eq!(expr.n_outputs(), 1);
+    }
+
+    #[test]
+    fn new_with_no_placeholders_has_zero_inputs() {
+        let expr = Expr::new("1 + 1").unwrap();
+        assert_eq!(expr.n_inputs(), 0);
+    }
+
+    #[test]
+    fn new_rejects_invalid_rust_expression() {
+        assert!(matches!(Expr::new("+ +"), Err(NewExprError::Parse(_))));
+    }
+
+    #[test]
+    fn substitute_replaces_each_placeholder_with_its_arg() {
+        let template: syn::Expr = syn::parse_str("__gantz_in0 + __gantz_in1").unwrap();
+        let args: Vec<syn::Expr> = vec![
+            syn::parse_str("a").unwrap(),
+            syn::parse_str("b").unwrap(),
+        ];
+        let result = substitute(&template, &args);
+        let rendered = quote::quote! { #result }.to_string();
+        assert_eq!(rendered, quote::quote! { a + b }.to_string());
+    }
+
+    #[test]
+    fn substitute_leaves_placeholders_without_an_arg_untouched() {
+        let template: syn::Expr = syn::parse_str("__gantz_in0").unwrap();
+        let args: Vec<syn::Expr> = vec![];
+        let result = substitute(&template, &args);
+        let rendered = quote::quote! { #result }.to_string();
+        assert_eq!(rendered, quote::quote! { __gantz_in0 }.to_string());
+    }
+}
@@ -8,6 +8,7 @@ pub mod pull;
 pub mod push;
 pub mod serde;
 pub mod state;
+pub mod trace;
 
 pub use self::deps::{Deps, WithCrateDeps};
 pub use self::expr::{Expr, NewExprError};
@@ -15,6 +16,7 @@ pub use self::pull::{Pull, WithPullEval};
 pub use self::push::{Push, WithPushEval};
 pub use self::serde::SerdeNode;
 pub use self::state::{State, WithStateType};
+pub use self::trace::{NodeId, NodeTracer, NoopTracer, TraceConfig};
 
 /// Gantz allows for constructing executable directed graphs by composing together **Node**s.
 ///
@@ -94,6 +96,93 @@ pub trait Node {
     fn crate_deps(&self) -> Vec<CrateDep> {
         vec![]
     }
+
+    /// A fallback expression to use for the given input when it is left unconnected within a
+    /// graph.
+    ///
+    /// When `None` is returned (the default) and the input is not `input_required`,
+    /// `Default::default()` is used in its place.
+    ///
+    /// By default, no fallback is provided.
+    fn input_default(&self, _input: Input) -> Option<syn::Expr> {
+        None
+    }
+
+    /// Whether or not the given input must be connected for this node to be evaluated.
+    ///
+    /// When a required input has no connection, code generation will skip evaluating this node
+    /// entirely rather than produce a call with a missing argument.
+    ///
+    /// By default, every input is required. See issue #17.
+    fn input_required(&self, _input: Input) -> bool {
+        true
+    }
+
+    /// A human-readable name for the given input, for use by editors and diagnostics that would
+    /// otherwise only be able to refer to the port by its index.
+    ///
+    /// By default, this is derived from the node's `Evaluator` - for `Evaluator::Fn` nodes, the
+    /// name of the corresponding argument pattern is used wherever it is a simple ident.
+    fn input_name(&self, input: Input) -> Option<String> {
+        self.evaluator().input_name(input)
+    }
+
+    /// A human-readable name for the given output. See `Node::input_name`.
+    fn output_name(&self, output: Output) -> Option<String> {
+        self.evaluator().output_name(output)
+    }
+
+    /// A hint as to the rust type expected at the given input, for use by editors and
+    /// diagnostics.
+    ///
+    /// By default, this is derived from the node's `Evaluator` - for `Evaluator::Fn` nodes, the
+    /// type of the corresponding argument is used.
+    fn input_ty_hint(&self, input: Input) -> Option<syn::Type> {
+        self.evaluator().input_ty_hint(input)
+    }
+
+    /// A hint as to the rust type produced at the given output. See `Node::input_ty_hint`.
+    fn output_ty_hint(&self, output: Output) -> Option<syn::Type> {
+        self.evaluator().output_ty_hint(output)
+    }
+
+    /// Documentation describing the purpose of the given port, for use in tooltips and other
+    /// editor affordances.
+    ///
+    /// By default, no documentation is provided.
+    fn port_docs(&self, _port: Port) -> Option<String> {
+        None
+    }
+
+    /// Aggregated metadata for the given input. See `PortInfo`.
+    fn input_port_info(&self, input: Input) -> PortInfo {
+        PortInfo {
+            name: self.input_name(input),
+            ty_hint: self.input_ty_hint(input),
+            docs: self.port_docs(Port::Input(input)),
+        }
+    }
+
+    /// Aggregated metadata for the given output. See `PortInfo`.
+    fn output_port_info(&self, output: Output) -> PortInfo {
+        PortInfo {
+            name: self.output_name(output),
+            ty_hint: self.output_ty_hint(output),
+            docs: self.port_docs(Port::Output(output)),
+        }
+    }
+
+    /// Whether or not this node's evaluation expression must be awaited.
+    ///
+    /// When `true`, the expression produced by this node's `Evaluator` is wrapped in `.await`,
+    /// and any generated `push_eval`/`pull_eval` function whose connected component contains
+    /// this node will itself be generated as an `async fn`.
+    ///
+    /// By default, this is derived from the node's `Evaluator` - for `Evaluator::Fn` nodes, this
+    /// is `true` if the function's signature is declared `async`.
+    fn is_async(&self) -> bool {
+        self.evaluator().is_async()
+    }
 }
 
 /// The method of evaluation used for a node.
@@ -117,8 +206,8 @@ pub enum Evaluator {
     /// Expressions have the benefit of not needing to know the exact types of a node's inputs and
     /// outputs. This simplifies the implementation of the `Node` trait for users.
     Expr {
-        /// The function for producing an expression given the input expressions.
-        gen_expr: Box<dyn Fn(Vec<syn::Expr>) -> syn::Expr>,
+        /// The means of producing the final expression given the input expressions.
+        gen_expr: ExprGen,
         /// The number of inputs to the expression.
         n_inputs: u32,
         /// The number of outputs to the expression.
@@ -126,11 +215,35 @@ pub enum Evaluator {
     },
 }
 
+/// The means by which an `Evaluator::Expr` produces its final expression given its input
+/// expressions.
+#[derive(Clone)]
+pub enum ExprGen {
+    /// A placeholder-substitution template, e.g. one parsed via `Expr::new`.
+    ///
+    /// Input placeholders take the form of reserved idents `__gantz_in0`, `__gantz_in1`, etc,
+    /// each of which is replaced with its corresponding input expression at evaluation time. As
+    /// the template is retained as data rather than captured within a closure, this
+    /// representation is serializable.
+    Template(Box<syn::Expr>),
+    /// An arbitrary closure for producing an expression given the input expressions.
+    ///
+    /// More flexible than `Template`, but cannot be serialized.
+    Fn(std::rc::Rc<dyn Fn(Vec<syn::Expr>) -> syn::Expr>),
+}
+
 /// Items that need to be known in order to generate a push evaluation function for a node.
 ///
 /// Note that all function signatures will have a single `node_states: node::States` argument
 /// appended to their `inputs` list in order to ensure the state associated with each node may be
-/// passed down the call stack. This means that when loading the symbol for the
+/// passed down the call stack.
+///
+/// `signature` may be declared `async`. Codegen that walks the connected component rooted at this
+/// `EvalFn` is responsible for computing whether any node within it is async (see
+/// `Node::is_async`) and, if so, calling `EvalFn::into_async` before emitting the function so that
+/// it is itself declared `async fn` and able to `.await` that node's call (see `Evaluator::expr`).
+/// `node_states` is threaded through the async call stack exactly as it is for the synchronous
+/// case - only the function and call expressions gain `async`/`.await`.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
 pub struct EvalFn {
     /// The type for each argument.
@@ -141,7 +254,23 @@ pub struct EvalFn {
     pub fn_attrs: Vec<syn::Attribute>,
 }
 
-/// Describes a crate dependency required by a node's generated and code.
+impl EvalFn {
+    /// Mark this `EvalFn`'s signature as `async`.
+    ///
+    /// Codegen should call this wherever any node within the connected component being generated
+    /// reports `true` from `Node::is_async`, so that the emitted function is declared `async fn`
+    /// and can `.await` that node's call (see `Evaluator::expr`).
+    pub fn into_async(mut self) -> Self {
+        self.signature.asyncness = Some(<syn::Token![async]>::default());
+        self
+    }
+}
+
+/// Describes a crate dependency required by a node's generated code.
+///
+/// `CrateDep` mirrors the fields available to a `[dependencies]` entry in a `Cargo.toml`
+/// manifest, supporting both crates.io and git dependencies. See `FromStr` for the supported
+/// textual forms and `to_toml_entry` for producing a manifest-ready line from a `CrateDep`.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
 pub struct CrateDep {
     /// The name of the crate.
@@ -151,23 +280,86 @@ pub struct CrateDep {
     ///
     /// E.g. "foo".
     pub name: String,
-    /// The source of the crate.
-    ///
-    /// This should be the same as the right-hand side of a `[dependencies]` entry as entered in a
-    /// `Cargo.toml` file.
-    ///
-    /// E.g. from crates.io:
-    ///
-    /// ```text
-    /// "0.10"
-    /// ```
-    ///
-    /// From a git repository:
+    /// A version requirement, e.g. `"0.10"`, for a crates.io dependency.
+    pub version: Option<String>,
+    /// A git repository URL, for a git dependency.
+    pub git: Option<String>,
+    /// A branch to check out from the `git` repository.
+    pub branch: Option<String>,
+    /// A tag to check out from the `git` repository.
+    pub tag: Option<String>,
+    /// A specific commit to check out from the `git` repository.
+    pub rev: Option<String>,
+    /// Additional cargo features to enable for the dependency.
+    pub features: Vec<String>,
+    /// Whether or not the dependency's default features should be enabled.
+    pub default_features: bool,
+}
+
+impl Default for CrateDep {
+    fn default() -> Self {
+        CrateDep {
+            name: String::new(),
+            version: None,
+            git: None,
+            branch: None,
+            tag: None,
+            rev: None,
+            features: vec![],
+            default_features: true,
+        }
+    }
+}
+
+impl CrateDep {
+    /// Produce a single valid `[dependencies]` manifest line for this dependency.
     ///
-    /// ```text
-    /// { git = "https://github.com/foo/bar", branch = "master" }
-    /// ```
-    pub source: String,
+    /// Uses the simple `name = "version"` form wherever only a version requirement is present,
+    /// falling back to the inline-table form (e.g. `name = { git = "...", features = ["a"] }`)
+    /// wherever any other field is set.
+    pub fn to_toml_entry(&self) -> String {
+        if self.git.is_none()
+            && self.branch.is_none()
+            && self.tag.is_none()
+            && self.rev.is_none()
+            && self.features.is_empty()
+            && self.default_features
+        {
+            if let Some(ref version) = self.version {
+                return format!("{} = \"{}\"", self.name, version);
+            }
+        }
+
+        let mut fields = vec![];
+        if let Some(ref version) = self.version {
+            fields.push(format!("version = \"{version}\""));
+        }
+        if let Some(ref git) = self.git {
+            fields.push(format!("git = \"{git}\""));
+        }
+        if let Some(ref branch) = self.branch {
+            fields.push(format!("branch = \"{branch}\""));
+        }
+        if let Some(ref tag) = self.tag {
+            fields.push(format!("tag = \"{tag}\""));
+        }
+        if let Some(ref rev) = self.rev {
+            fields.push(format!("rev = \"{rev}\""));
+        }
+        if !self.features.is_empty() {
+            let features = self
+                .features
+                .iter()
+                .map(|f| format!("\"{f}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            fields.push(format!("features = [{features}]"));
+        }
+        if !self.default_features {
+            fields.push("default-features = false".to_string());
+        }
+        format!("{} = {{ {} }}", self.name, fields.join(", "))
+    }
 }
 
 /// Represents an input of a node via an index.
@@ -178,6 +370,43 @@ pub struct Input(pub u32);
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
 pub struct Output(pub u32);
 
+/// A reference to either an input or an output of a node, for methods that describe ports
+/// generically (e.g. `Node::port_docs`).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Port {
+    /// An input port.
+    Input(Input),
+    /// An output port.
+    Output(Output),
+}
+
+/// Aggregated metadata describing a single input or output port.
+///
+/// Allows downstream tooling (editors, error messages) to describe a port by its name and type
+/// rather than only by its index - e.g. an error might read "output `sum: f32` -> input `lhs:
+/// i32`" rather than "output 0 -> input 0".
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Deserialize, Serialize)]
+pub struct PortInfo {
+    /// The port's name, if known.
+    pub name: Option<String>,
+    /// A hint as to the port's rust type, if known.
+    #[serde(with = "crate::node::serde::opt_type")]
+    pub ty_hint: Option<syn::Type>,
+    /// Documentation describing the port's purpose, if provided.
+    pub docs: Option<String>,
+}
+
+impl std::fmt::Display for PortInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.name, &self.ty_hint) {
+            (Some(name), Some(ty)) => write!(f, "{}: {}", name, quote::quote! { #ty }),
+            (Some(name), None) => write!(f, "{}", name),
+            (None, Some(ty)) => write!(f, "{}", quote::quote! { #ty }),
+            (None, None) => write!(f, "<unnamed>"),
+        }
+    }
+}
+
 /// Failure to parse a `str` as a `CrateDep`.
 #[derive(Clone, Debug, Error)]
 #[error("failed to parse the `str` as a valid `CrateDep`")]
@@ -200,15 +429,110 @@ impl Evaluator {
         }
     }
 
-    /// Tokens representing the rust code that will evaluate to a tuple containing all outputs.
+    /// Tokens representing the rust code that will evaluate to a tuple containing all outputs,
+    /// or `None` if the node should not be evaluated at all.
     ///
-    /// TODO: Handle case where only a subset of inputs are connected. See issue #17.
-    pub fn expr(&self, args: Vec<syn::Expr>, stateful: bool) -> syn::Expr {
-        match *self {
+    /// `args[i]` is the expression connected to `Input(i)`, or `None` if that input is
+    /// unconnected (see issue #17). For each unconnected input, `input_required(Input(i))` is
+    /// consulted first: if `true`, evaluation of this node is skipped entirely and `None` is
+    /// returned, since there is no sensible expression to fall back to. Otherwise the input is
+    /// resolved to `input_default(Input(i))` if provided, or to `Default::default()`.
+    ///
+    /// If `is_async` (see `Node::is_async`), the resulting expression is wrapped in `.await`.
+    pub fn expr(
+        &self,
+        args: Vec<Option<syn::Expr>>,
+        input_default: impl Fn(Input) -> Option<syn::Expr>,
+        input_required: impl Fn(Input) -> bool,
+        stateful: bool,
+        is_async: bool,
+    ) -> Option<syn::Expr> {
+        for (i, arg) in args.iter().enumerate() {
+            if arg.is_none() && input_required(Input(i as u32)) {
+                return None;
+            }
+        }
+        let args = resolve_args(args, &input_default);
+        let expr = match *self {
             Evaluator::Fn { ref fn_item } => fn_call_expr(fn_item, args, stateful),
-            Evaluator::Expr { ref gen_expr, .. } => (*gen_expr)(args),
+            Evaluator::Expr { ref gen_expr, .. } => match gen_expr {
+                ExprGen::Template(template) => self::expr::substitute(template, &args),
+                ExprGen::Fn(gen_expr) => (*gen_expr)(args),
+            },
+        };
+        let expr = if is_async {
+            // `.await` binds tighter than most operators (e.g. `a + b.await` parses as
+            // `a + (b.await)`), so `expr` must be parenthesized before appending it regardless of
+            // what kind of expression it is.
+            syn::parse_quote! { (#expr).await }
+        } else {
+            expr
+        };
+        Some(expr)
+    }
+
+    /// Whether or not this evaluator's expression must be awaited.
+    ///
+    /// For `Evaluator::Fn`, this is `true` if the function's signature is declared `async`. The
+    /// `Evaluator::Expr` variant has no inherent notion of asyncness - see `Node::is_async` for
+    /// overriding this on a per-node basis.
+    pub fn is_async(&self) -> bool {
+        match *self {
+            Evaluator::Fn { ref fn_item } => fn_item.sig.asyncness.is_some(),
+            Evaluator::Expr { .. } => false,
+        }
+    }
+
+    /// The name of the given input, derived from its argument pattern for `Evaluator::Fn` nodes.
+    ///
+    /// Always `None` for `Evaluator::Expr` nodes, as expression templates have no named inputs.
+    pub fn input_name(&self, input: Input) -> Option<String> {
+        match *self {
+            Evaluator::Fn { ref fn_item } => fn_input_ident(&fn_item.sig, input),
+            Evaluator::Expr { .. } => None,
+        }
+    }
+
+    /// The name of the given output.
+    ///
+    /// Functions have no means of naming individual return values or tuple elements, so this is
+    /// always `None`.
+    pub fn output_name(&self, _output: Output) -> Option<String> {
+        None
+    }
+
+    /// A hint as to the rust type of the given input, derived from the argument's type for
+    /// `Evaluator::Fn` nodes.
+    pub fn input_ty_hint(&self, input: Input) -> Option<syn::Type> {
+        match *self {
+            Evaluator::Fn { ref fn_item } => fn_input_ty(&fn_item.sig, input),
+            Evaluator::Expr { .. } => None,
         }
     }
+
+    /// A hint as to the rust type of the given output, derived from the return type (unpacking
+    /// one level of tuple) for `Evaluator::Fn` nodes.
+    pub fn output_ty_hint(&self, output: Output) -> Option<syn::Type> {
+        match *self {
+            Evaluator::Fn { ref fn_item } => fn_output_ty(&fn_item.sig, output),
+            Evaluator::Expr { .. } => None,
+        }
+    }
+}
+
+// Resolve each unconnected (`None`) argument to its input's default expression, falling back to
+// `Default::default()` where no default is specified.
+fn resolve_args(
+    args: Vec<Option<syn::Expr>>,
+    input_default: &dyn Fn(Input) -> Option<syn::Expr>,
+) -> Vec<syn::Expr> {
+    args.into_iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            arg.or_else(|| input_default(Input(i as u32)))
+                .unwrap_or_else(|| syn::parse_quote! { Default::default() })
+        })
+        .collect()
 }
 
 impl<'a, N> Node for &'a N
@@ -234,6 +558,38 @@ where
     fn crate_deps(&self) -> Vec<CrateDep> {
         (**self).crate_deps()
     }
+
+    fn input_default(&self, input: Input) -> Option<syn::Expr> {
+        (**self).input_default(input)
+    }
+
+    fn input_required(&self, input: Input) -> bool {
+        (**self).input_required(input)
+    }
+
+    fn input_name(&self, input: Input) -> Option<String> {
+        (**self).input_name(input)
+    }
+
+    fn output_name(&self, output: Output) -> Option<String> {
+        (**self).output_name(output)
+    }
+
+    fn input_ty_hint(&self, input: Input) -> Option<syn::Type> {
+        (**self).input_ty_hint(input)
+    }
+
+    fn output_ty_hint(&self, output: Output) -> Option<syn::Type> {
+        (**self).output_ty_hint(output)
+    }
+
+    fn port_docs(&self, port: Port) -> Option<String> {
+        (**self).port_docs(port)
+    }
+
+    fn is_async(&self) -> bool {
+        (**self).is_async()
+    }
 }
 
 macro_rules! impl_node_for_ptr {
@@ -258,6 +614,38 @@ macro_rules! impl_node_for_ptr {
             fn crate_deps(&self) -> Vec<CrateDep> {
                 (**self).crate_deps()
             }
+
+            fn input_default(&self, input: Input) -> Option<syn::Expr> {
+                (**self).input_default(input)
+            }
+
+            fn input_required(&self, input: Input) -> bool {
+                (**self).input_required(input)
+            }
+
+            fn input_name(&self, input: Input) -> Option<String> {
+                (**self).input_name(input)
+            }
+
+            fn output_name(&self, output: Output) -> Option<String> {
+                (**self).output_name(output)
+            }
+
+            fn input_ty_hint(&self, input: Input) -> Option<syn::Type> {
+                (**self).input_ty_hint(input)
+            }
+
+            fn output_ty_hint(&self, output: Output) -> Option<syn::Type> {
+                (**self).output_ty_hint(output)
+            }
+
+            fn port_docs(&self, port: Port) -> Option<String> {
+                (**self).port_docs(port)
+            }
+
+            fn is_async(&self) -> bool {
+                (**self).is_async()
+            }
         }
     };
 }
@@ -295,11 +683,56 @@ impl From<u32> for Output {
 impl FromStr for CrateDep {
     type Err = ParseCrateDepError;
 
+    // Supports both the simple `name = "0.10"` form and the inline-table form
+    // `name = { git = "...", features = ["a", "b"] }`. Neither a bare scalar nor a bare
+    // inline-table is a valid standalone TOML document on its own, so `s` is parsed in full (it
+    // already is a valid single-entry document) and the dependency's name is recovered from the
+    // resulting table's one key, rather than splitting and parsing just the right-hand side.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut sides = s.split('=');
-        let name = sides.next().ok_or(ParseCrateDepError)?.trim().to_string();
-        let source = sides.next().ok_or(ParseCrateDepError)?.trim().to_string();
-        Ok(CrateDep { name, source })
+        let doc: toml::Value = s.parse().map_err(|_| ParseCrateDepError)?;
+        let table = doc.as_table().ok_or(ParseCrateDepError)?;
+        let (name, value) = table.iter().next().ok_or(ParseCrateDepError)?;
+        let name = name.clone();
+        let value = value.clone();
+
+        let mut dep = CrateDep {
+            name,
+            ..CrateDep::default()
+        };
+        match value {
+            toml::Value::String(version) => {
+                dep.version = Some(version);
+            }
+            toml::Value::Table(table) => {
+                let as_string = |key: &str| {
+                    table
+                        .get(key)
+                        .and_then(toml::Value::as_str)
+                        .map(str::to_string)
+                };
+                dep.version = as_string("version");
+                dep.git = as_string("git");
+                dep.branch = as_string("branch");
+                dep.tag = as_string("tag");
+                dep.rev = as_string("rev");
+                dep.features = table
+                    .get("features")
+                    .and_then(toml::Value::as_array)
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(toml::Value::as_str)
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                dep.default_features = table
+                    .get("default-features")
+                    .and_then(toml::Value::as_bool)
+                    .unwrap_or(true);
+            }
+            _ => return Err(ParseCrateDepError),
+        }
+        Ok(dep)
     }
 }
 
@@ -330,6 +763,41 @@ fn count_fn_outputs(signature: &syn::Signature) -> usize {
     }
 }
 
+// The argument pattern and type at the given input index within `signature`, if any.
+fn fn_input_arg(signature: &syn::Signature, input: Input) -> Option<&syn::PatType> {
+    match signature.inputs.iter().nth(input.0 as usize)? {
+        syn::FnArg::Typed(pat_type) => Some(pat_type),
+        syn::FnArg::Receiver(_) => None,
+    }
+}
+
+// The name of the argument at the given input index within `signature`, if it is a simple ident
+// pattern (e.g. not a destructuring pattern).
+fn fn_input_ident(signature: &syn::Signature, input: Input) -> Option<String> {
+    match &*fn_input_arg(signature, input)?.pat {
+        syn::Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+        _ => None,
+    }
+}
+
+// The type of the argument at the given input index within `signature`.
+fn fn_input_ty(signature: &syn::Signature, input: Input) -> Option<syn::Type> {
+    Some((*fn_input_arg(signature, input)?.ty).clone())
+}
+
+// The type of the given output index within `signature`'s return type, unpacking one level of
+// tuple in the case of multiple outputs.
+fn fn_output_ty(signature: &syn::Signature, output: Output) -> Option<syn::Type> {
+    match signature.output {
+        syn::ReturnType::Default => None,
+        syn::ReturnType::Type(_, ref ty) => match **ty {
+            syn::Type::Tuple(ref tuple) => tuple.elems.iter().nth(output.0 as usize).cloned(),
+            _ if output.0 == 0 => Some((**ty).clone()),
+            _ => None,
+        },
+    }
+}
+
 // Create a rust expression that calls the given `signature` function with the given `args`
 // expressions as its inputs.
 fn fn_call_expr(fn_item: &syn::ItemFn, args: Vec<syn::Expr>, stateful: bool) -> syn::Expr {
@@ -370,3 +838,60 @@ fn fn_call_expr(fn_item: &syn::ItemFn, args: Vec<syn::Expr>, stateful: bool) ->
     let expr = syn::Expr::Call(expr_call);
     expr
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CrateDep;
+
+    #[test]
+    fn to_toml_entry_simple_version() {
+        let dep = CrateDep {
+            name: "foo".to_string(),
+            version: Some("0.10".to_string()),
+            ..CrateDep::default()
+        };
+        assert_eq!(dep.to_toml_entry(), r#"foo = "0.10""#);
+    }
+
+    #[test]
+    fn parse_simple_version_round_trips() {
+        let entry = r#"foo = "0.10""#;
+        let dep: CrateDep = entry.parse().unwrap();
+        assert_eq!(dep.name, "foo");
+        assert_eq!(dep.version.as_deref(), Some("0.10"));
+        assert!(dep.default_features);
+        assert_eq!(dep.to_toml_entry(), entry);
+    }
+
+    #[test]
+    fn parse_git_dep_with_features_round_trips() {
+        let entry =
+            r#"foo = { git = "https://example.com/foo", branch = "main", features = ["a", "b"], default-features = false }"#;
+        let dep: CrateDep = entry.parse().unwrap();
+        assert_eq!(dep.name, "foo");
+        assert_eq!(dep.git.as_deref(), Some("https://example.com/foo"));
+        assert_eq!(dep.branch.as_deref(), Some("main"));
+        assert_eq!(dep.features, vec!["a".to_string(), "b".to_string()]);
+        assert!(!dep.default_features);
+        assert_eq!(dep.to_toml_entry(), entry);
+    }
+
+    #[test]
+    fn version_with_non_default_fields_uses_inline_table_form() {
+        let dep = CrateDep {
+            name: "foo".to_string(),
+            version: Some("0.10".to_string()),
+            features: vec!["a".to_string()],
+            ..CrateDep::default()
+        };
+        assert_eq!(
+            dep.to_toml_entry(),
+            r#"foo = { version = "0.10", features = ["a"] }"#
+        );
+    }
+
+    #[test]
+    fn parse_rejects_non_table_non_string_rhs() {
+        assert!("foo = 1".parse::<CrateDep>().is_err());
+    }
+}
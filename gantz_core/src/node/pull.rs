@@ -0,0 +1,82 @@
+use super::{CrateDep, EvalFn, Evaluator, Node};
+
+/// A node wrapper that enables pull evaluation for the inner node.
+///
+/// See [`WithPullEval::with_pull_eval`].
+#[derive(Clone, Debug)]
+pub struct Pull<N> {
+    node: N,
+    eval_fn: EvalFn,
+}
+
+/// Node methods for enabling pull evaluation.
+pub trait WithPullEval: Node + Sized {
+    /// Specify the function that should be generated to allow pulling evaluation from this node.
+    ///
+    /// See [`Node::pull_eval`].
+    fn with_pull_eval(self, eval_fn: EvalFn) -> Pull<Self> {
+        Pull {
+            node: self,
+            eval_fn,
+        }
+    }
+}
+
+impl<N> WithPullEval for N where N: Node {}
+
+impl<N> Node for Pull<N>
+where
+    N: Node,
+{
+    fn evaluator(&self) -> Evaluator {
+        self.node.evaluator()
+    }
+
+    fn push_eval(&self) -> Option<EvalFn> {
+        self.node.push_eval()
+    }
+
+    fn pull_eval(&self) -> Option<EvalFn> {
+        Some(self.eval_fn.clone())
+    }
+
+    fn state_type(&self) -> Option<syn::Type> {
+        self.node.state_type()
+    }
+
+    fn crate_deps(&self) -> Vec<CrateDep> {
+        self.node.crate_deps()
+    }
+
+    fn input_default(&self, input: super::Input) -> Option<syn::Expr> {
+        self.node.input_default(input)
+    }
+
+    fn input_required(&self, input: super::Input) -> bool {
+        self.node.input_required(input)
+    }
+
+    fn input_name(&self, input: super::Input) -> Option<String> {
+        self.node.input_name(input)
+    }
+
+    fn output_name(&self, output: super::Output) -> Option<String> {
+        self.node.output_name(output)
+    }
+
+    fn input_ty_hint(&self, input: super::Input) -> Option<syn::Type> {
+        self.node.input_ty_hint(input)
+    }
+
+    fn output_ty_hint(&self, output: super::Output) -> Option<syn::Type> {
+        self.node.output_ty_hint(output)
+    }
+
+    fn port_docs(&self, port: super::Port) -> Option<String> {
+        self.node.port_docs(port)
+    }
+
+    fn is_async(&self) -> bool {
+        self.node.is_async()
+    }
+}
@@ -0,0 +1,128 @@
+//! `serde::with` adapters for the `syn` types used throughout `node`, none of which implement
+//! `Serialize`/`Deserialize` themselves.
+//!
+//! Each adapter (de)serializes its `syn` type via the `String` produced by rendering its token
+//! stream, re-parsing that `String` on the way back in.
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// (De)serialize a `syn::Signature`, e.g. the signature field of `EvalFn`.
+pub mod signature {
+    use super::*;
+
+    pub fn serialize<S>(signature: &syn::Signature, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        quote::quote! { #signature }.to_string().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<syn::Signature, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let tokens = String::deserialize(deserializer)?;
+        syn::parse_str(&tokens).map_err(::serde::de::Error::custom)
+    }
+}
+
+/// (De)serialize a `Vec<syn::Attribute>`, e.g. the `fn_attrs` field of `EvalFn`.
+pub mod fn_attrs {
+    use super::*;
+    use syn::parse::Parser;
+
+    pub fn serialize<S>(attrs: &[syn::Attribute], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let tokens = attrs
+            .iter()
+            .map(|attr| quote::quote! { #attr }.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        tokens.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<syn::Attribute>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let tokens = String::deserialize(deserializer)?;
+        syn::Attribute::parse_outer
+            .parse_str(&tokens)
+            .map_err(::serde::de::Error::custom)
+    }
+}
+
+/// (De)serialize a `syn::Expr`, e.g. the template field of a [`crate::node::Expr`].
+pub mod expr {
+    use super::*;
+
+    pub fn serialize<S>(expr: &syn::Expr, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        quote::quote! { #expr }.to_string().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<syn::Expr, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let tokens = String::deserialize(deserializer)?;
+        syn::parse_str(&tokens).map_err(::serde::de::Error::custom)
+    }
+}
+
+/// (De)serialize an `Option<syn::Type>`, e.g. the `ty_hint` field of [`crate::node::PortInfo`].
+pub mod opt_type {
+    use super::*;
+
+    pub fn serialize<S>(ty: &Option<syn::Type>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ty.as_ref()
+            .map(|ty| quote::quote! { #ty }.to_string())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<syn::Type>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let tokens = Option::<String>::deserialize(deserializer)?;
+        tokens
+            .map(|s| syn::parse_str(&s).map_err(::serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+/// A serializable representation of a node, used so that graphs composed of trait objects (`Box<
+/// dyn Node>`) can be saved and reloaded.
+///
+/// Variants mirror the two [`super::Evaluator`] kinds that support serialization - notice that
+/// there is no equivalent of the closure-based [`super::ExprGen::Fn`], as closures cannot be
+/// serialized.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum SerdeNode {
+    /// A node whose evaluator is a named, free-standing function.
+    Fn(FnNode),
+    /// A node whose evaluator is a placeholder-substitution expression template.
+    Expr(Box<crate::node::Expr>),
+}
+
+/// The serializable fields of a function-based node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FnNode {
+    /// The node's function signature.
+    #[serde(with = "signature")]
+    pub signature: syn::Signature,
+    /// Attributes for the generated `ItemFn`.
+    #[serde(with = "fn_attrs")]
+    pub fn_attrs: Vec<syn::Attribute>,
+    /// The node's push evaluation function, if enabled.
+    pub push_eval: Option<crate::node::EvalFn>,
+    /// The node's pull evaluation function, if enabled.
+    pub pull_eval: Option<crate::node::EvalFn>,
+}
@@ -0,0 +1,82 @@
+use super::{CrateDep, EvalFn, Evaluator, Node};
+
+/// A node wrapper that associates a persistent state type with the inner node.
+///
+/// See [`WithStateType::with_state_type`].
+#[derive(Clone, Debug)]
+pub struct State<N> {
+    node: N,
+    state_type: syn::Type,
+}
+
+/// Node methods for specifying a persistent state type.
+pub trait WithStateType: Node + Sized {
+    /// Specify the type of persistent state required by this node's expression.
+    ///
+    /// See [`Node::state_type`].
+    fn with_state_type(self, state_type: syn::Type) -> State<Self> {
+        State {
+            node: self,
+            state_type,
+        }
+    }
+}
+
+impl<N> WithStateType for N where N: Node {}
+
+impl<N> Node for State<N>
+where
+    N: Node,
+{
+    fn evaluator(&self) -> Evaluator {
+        self.node.evaluator()
+    }
+
+    fn push_eval(&self) -> Option<EvalFn> {
+        self.node.push_eval()
+    }
+
+    fn pull_eval(&self) -> Option<EvalFn> {
+        self.node.pull_eval()
+    }
+
+    fn state_type(&self) -> Option<syn::Type> {
+        Some(self.state_type.clone())
+    }
+
+    fn crate_deps(&self) -> Vec<CrateDep> {
+        self.node.crate_deps()
+    }
+
+    fn input_default(&self, input: super::Input) -> Option<syn::Expr> {
+        self.node.input_default(input)
+    }
+
+    fn input_required(&self, input: super::Input) -> bool {
+        self.node.input_required(input)
+    }
+
+    fn input_name(&self, input: super::Input) -> Option<String> {
+        self.node.input_name(input)
+    }
+
+    fn output_name(&self, output: super::Output) -> Option<String> {
+        self.node.output_name(output)
+    }
+
+    fn input_ty_hint(&self, input: super::Input) -> Option<syn::Type> {
+        self.node.input_ty_hint(input)
+    }
+
+    fn output_ty_hint(&self, output: super::Output) -> Option<syn::Type> {
+        self.node.output_ty_hint(output)
+    }
+
+    fn port_docs(&self, port: super::Port) -> Option<String> {
+        self.node.port_docs(port)
+    }
+
+    fn is_async(&self) -> bool {
+        self.node.is_async()
+    }
+}
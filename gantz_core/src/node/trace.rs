@@ -0,0 +1,125 @@
+use super::{Input, Node, Output};
+
+/// Identifies a node within a graph for the purposes of tracing.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct NodeId(pub u32);
+
+/// A hook for observing each node's inputs and outputs as a `push_eval`/`pull_eval` function
+/// executes, for use by debug/trace-instrumented builds.
+///
+/// Codegen should accept a `tracer: &mut dyn NodeTracer` argument, alongside the existing
+/// `node_states` argument, on any generated function wherever `TraceConfig` enables
+/// instrumentation for a graph, and wrap each node's evaluation expression with `wrap_traced`.
+pub trait NodeTracer {
+    /// Called immediately before a node's expression is evaluated.
+    ///
+    /// `node_id` identifies the node within its graph and `inputs` holds, for each of its
+    /// (possibly unconnected) inputs, the source expression connected to it as rendered at code
+    /// generation time, see `labeled_inputs`. The runtime values of the inputs are not rendered.
+    fn on_enter(&mut self, node_id: NodeId, inputs: &[Option<String>]) {
+        let _ = (node_id, inputs);
+    }
+
+    /// Called immediately after a node's expression has been evaluated.
+    ///
+    /// `outputs` is a debug rendering of each of the node's output values.
+    fn on_exit(&mut self, node_id: NodeId, outputs: &[String]) {
+        let _ = (node_id, outputs);
+    }
+}
+
+/// A `NodeTracer` that performs no instrumentation.
+///
+/// Each hook keeps the default, empty `NodeTracer` body. Where `TraceConfig` disables
+/// instrumentation, `wrap_traced` emits no tracer calls at all, so no tracer is involved.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopTracer;
+
+impl NodeTracer for NoopTracer {}
+
+/// Configuration for enabling instrumented ("debug") code generation.
+///
+/// Passed to `wrap_traced`, which wraps a node's evaluation expression with calls to a `&mut dyn
+/// NodeTracer` binding when `enabled`, so that non-debug builds pay no runtime cost for tracing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TraceConfig {
+    /// Whether or not instrumentation should be generated at all.
+    pub enabled: bool,
+}
+
+/// Pair each of `node`'s input expressions with its `Node::input_name` (if any), rendering the
+/// expression's tokens (not its runtime value) as a `String` suitable for passing to
+/// `wrap_traced`.
+///
+/// `args[i]` corresponds to `Input(i)`; `None` entries (unconnected inputs, see issue #17) are
+/// passed through as `None` rather than rendered.
+pub fn labeled_inputs(node: &impl Node, args: &[Option<syn::Expr>]) -> Vec<Option<String>> {
+    args.iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            arg.as_ref().map(|expr| {
+                let rendered = quote::quote! { #expr }.to_string();
+                match node.input_name(Input(i as u32)) {
+                    Some(name) => format!("{name} = {rendered}"),
+                    None => rendered,
+                }
+            })
+        })
+        .collect()
+}
+
+/// A name for each of `node`'s `n_outputs` outputs, for use as the `output_names` argument to
+/// `wrap_traced`. See `labeled_inputs`.
+///
+/// Unlike inputs, outputs have no separate source expression to render ahead of time, as a
+/// node's outputs are only known once its evaluation expression (see `Evaluator::expr`) has
+/// actually run. `wrap_traced` renders each output's value at runtime and pairs it with the name
+/// returned here.
+pub fn labeled_outputs(node: &impl Node, n_outputs: u32) -> Vec<Option<String>> {
+    (0..n_outputs).map(|i| node.output_name(Output(i))).collect()
+}
+
+/// Wrap `expr` - a node's evaluation expression, as produced by `Evaluator::expr` - with calls to
+/// a `&mut dyn NodeTracer` binding named `tracer`, which codegen must ensure is in scope alongside
+/// `state` wherever tracing is enabled for the containing graph.
+///
+/// `node_id` identifies the node for the tracer, `inputs` should be computed via `labeled_inputs`
+/// from the same args passed to `Evaluator::expr`, and `output_names` should be computed via
+/// `labeled_outputs`. Returns `expr` unmodified when `trace_config.enabled` is `false`.
+pub fn wrap_traced(
+    node_id: NodeId,
+    inputs: &[Option<String>],
+    output_names: &[Option<String>],
+    trace_config: TraceConfig,
+    expr: syn::Expr,
+) -> syn::Expr {
+    if !trace_config.enabled {
+        return expr;
+    }
+    let id = node_id.0;
+    let input_toks = inputs.iter().map(|input| match input {
+        Some(s) => quote::quote! { Some(#s.to_string()) },
+        None => quote::quote! { None },
+    });
+    let n_outputs = output_names.len();
+    let output_toks = output_names.iter().enumerate().map(|(i, name)| {
+        let value = if n_outputs <= 1 {
+            quote::quote! { __gantz_out }
+        } else {
+            let index = syn::Index::from(i);
+            quote::quote! { __gantz_out.#index }
+        };
+        match name {
+            Some(name) => quote::quote! { format!("{} = {:?}", #name, #value) },
+            None => quote::quote! { format!("{:?}", #value) },
+        }
+    });
+    syn::parse_quote! {
+        {
+            tracer.on_enter(::gantz_core::node::trace::NodeId(#id), &[#(#input_toks),*]);
+            let __gantz_out = #expr;
+            tracer.on_exit(::gantz_core::node::trace::NodeId(#id), &[#(#output_toks),*]);
+            __gantz_out
+        }
+    }
+}